@@ -0,0 +1,7 @@
+use unic_langid::LanguageIdentifier;
+
+/// Parses a CLI argument into a canonicalized BCP-47 `LanguageIdentifier`.
+pub fn parse_lang_tag(raw: &str) -> Result<LanguageIdentifier, String> {
+    raw.parse::<LanguageIdentifier>()
+        .map_err(|_| format!("'{}' is not a well-formed BCP-47 language tag", raw))
+}
@@ -1,8 +1,17 @@
 use clap::{Parser, Subcommand};
-use glob::glob;
-use std::path::PathBuf;
+use regex::Regex;
+use std::path::{Path, PathBuf};
 use std::process::exit;
-use translate_dir_lib::{project, project_config::ProjectConfig, Language}; // Add this import
+use translate_dir_lib::{
+    ignore::{walk_files, IgnoreMatcher},
+    project, project_config::ProjectConfig,
+};
+use unic_langid::LanguageIdentifier;
+
+mod lang_tag;
+mod pattern_kind;
+use lang_tag::parse_lang_tag;
+use pattern_kind::{glob_to_regex, parse_pattern_kind, PatternKind};
 
 #[derive(Parser, Debug)]
 #[clap(author = "Paris Innovation Laboratory", version, about = "CLI for document/directory translation", long_about = None)]
@@ -19,6 +28,11 @@ enum Commands {
         name: String,
         #[clap(short, long, value_parser, default_value = ".")]
         path: PathBuf,
+        /// Where translations are written: "mirrored" puts each target
+        /// language in its own directory, "suffix" writes `name.<lang>.ext`
+        /// next to the source file.
+        #[clap(long, value_enum, default_value = "mirrored")]
+        layout: OutputLayout,
     },
     #[clap(alias = "p")]
     Project {
@@ -29,49 +43,119 @@ enum Commands {
     },
 }
 
+/// The translation engine a project is configured to use.
+#[derive(clap::ValueEnum, Clone, Debug)]
+enum BackendKind {
+    Google,
+    Deepl,
+    Http,
+}
+
+/// Where a project writes its translations on disk.
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum OutputLayout {
+    /// Mirror the source tree into a directory per target language.
+    Mirrored,
+    /// Write `name.<lang>.ext` next to each source file.
+    Suffix,
+}
+
+impl OutputLayout {
+    fn as_str(self) -> &'static str {
+        match self {
+            OutputLayout::Mirrored => "mirrored",
+            OutputLayout::Suffix => "suffix",
+        }
+    }
+}
+
 #[derive(Subcommand, Debug)]
 enum ProjectAction {
     // ... SetSource, AddTargetLang, RemoveTargetLang, Sync ...
     SetSource {
         dir_name: String,
-        #[clap(value_enum)]
-        language: Language,
+        #[clap(value_parser = parse_lang_tag)]
+        language: LanguageIdentifier,
     },
     AddTargetLang {
-        #[clap(value_enum)]
-        language: Language,
+        #[clap(value_parser = parse_lang_tag)]
+        language: LanguageIdentifier,
     },
     RemoveTargetLang {
-        #[clap(value_enum)]
-        language: Language,
+        #[clap(value_parser = parse_lang_tag)]
+        language: LanguageIdentifier,
     },
     Sync,
 
     /// Marks one or more files/patterns in the source directory as translatable.
     /// Accepts multiple file paths or glob patterns (e.g., "*.txt", "docs/*.md").
+    /// Prefix a pattern with "glob:", "re:" or "path:" to pick the matching mode
+    /// explicitly, e.g. "re:.*\.(md|rst)$" or "path:weird[name].txt".
     /// Note: Shells might expand globs; quote them if needed: "src/*.rs"
     MarkTranslatable {
-        /// Paths or glob patterns of files to mark as translatable.
-        #[clap(required = true, num_args = 1..)]
+        /// Paths or patterns of files to mark as translatable. May be omitted
+        /// if --extension is given.
+        #[clap(num_args = 0..)]
         file_patterns: Vec<String>,
+        /// Comma-separated list of extensions or categories to bulk-mark
+        /// (e.g. "md,rst" or "text"), honoring `.translateignore`.
+        #[clap(long, alias = "type", value_delimiter = ',')]
+        extension: Vec<String>,
     },
     /// Marks one or more files/patterns in the source directory as untranslatable.
     /// Accepts multiple file paths or glob patterns (e.g., "*.log", "images/*").
+    /// Prefix a pattern with "glob:", "re:" or "path:" to pick the matching mode
+    /// explicitly, e.g. "re:.*\.(md|rst)$" or "path:weird[name].txt".
     /// Note: Shells might expand globs; quote them if needed: "config/*.json"
     MarkUntranslatable {
-        /// Paths or glob patterns of files to mark as untranslatable.
+        /// Paths or patterns of files to mark as untranslatable.
         #[clap(required = true, num_args = 1..)]
         file_patterns: Vec<String>,
     },
-    ListTranslatable,
+    /// Lists translatable files, honoring `.translateignore`.
+    ListTranslatable {
+        /// Comma-separated list of extensions or categories to filter by
+        /// (e.g. "md,rst" or "text").
+        #[clap(long, alias = "type", value_delimiter = ',')]
+        extension: Vec<String>,
+    },
     TranslateFile {
         file_path: PathBuf,
-        #[clap(value_enum)]
-        target_language: Language,
+        #[clap(value_parser = parse_lang_tag)]
+        target_language: LanguageIdentifier,
+        /// Re-translate even if the translation memory says this file is up to date.
+        #[clap(long)]
+        force: bool,
     },
     TranslateAll {
+        #[clap(value_parser = parse_lang_tag)]
+        target_language: LanguageIdentifier,
+        /// Re-translate every file even if the translation memory says it's up to date.
+        #[clap(long)]
+        force: bool,
+    },
+    /// Shows, per translatable file and target language, whether the
+    /// translation is up to date, stale, or missing, without calling the
+    /// translation backend.
+    Status,
+    /// Selects and configures the translation engine the project uses.
+    SetBackend {
         #[clap(value_enum)]
-        target_language: Language,
+        engine: BackendKind,
+        /// Name of the environment variable holding the engine's API key.
+        #[clap(long)]
+        api_key_env: Option<String>,
+        /// Base URL, required for the `http` engine.
+        #[clap(long)]
+        base_url: Option<String>,
+        /// Optional glossary to send with each translation request.
+        #[clap(long)]
+        glossary: Option<String>,
+    },
+    /// Changes where translations are written for an existing project.
+    SetLayout {
+        #[clap(value_enum)]
+        layout: OutputLayout,
     },
     Info,
 }
@@ -80,8 +164,8 @@ fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Init { name, path } => {
-            handle_init(&name, path);
+        Commands::Init { name, path, layout } => {
+            handle_init(&name, path, layout);
         }
         Commands::Project { action, path } => match project::load(path.clone()) {
             Ok(mut proj) => {
@@ -95,8 +179,8 @@ fn main() {
     }
 }
 
-fn handle_init(name: &str, path: PathBuf) {
-    match project::init(name, path.clone()) {
+fn handle_init(name: &str, path: PathBuf, layout: OutputLayout) {
+    match project::init(name, path.clone(), layout.as_str()) {
         Ok(_) => println!(
             "Successfully initialized project '{}' in '{}'",
             name,
@@ -109,6 +193,16 @@ fn handle_init(name: &str, path: PathBuf) {
     }
 }
 
+/// Recursively collects every file path under `root`, relative to `root`,
+/// for `glob:`/`re:` patterns to match against, honoring `.translateignore`
+/// and skipping the project's own config directory, same as the library's
+/// own source walks.
+fn walk_relative_files(proj: &project::Project, root: &Path) -> Vec<PathBuf> {
+    let ignore =
+        IgnoreMatcher::load(&proj.get_root_path()).unwrap_or_else(|_| IgnoreMatcher::empty());
+    walk_files(root, &ignore, &proj.get_config_dir_path())
+}
+
 fn process_file_patterns<F>(
     proj: &mut project::Project,
     file_patterns: Vec<String>,
@@ -124,86 +218,90 @@ fn process_file_patterns<F>(
     let mut error_count = 0;
     let mut no_match_patterns = Vec::new();
 
+    let mark_one = |path: PathBuf, proj: &mut project::Project| -> bool {
+        match action_fn(proj, path.clone()) {
+            Ok(_) => {
+                println!(
+                    "Successfully marked '{}' as {}.",
+                    path.display(),
+                    action_name
+                );
+                true
+            }
+            Err(e) => {
+                eprintln!(
+                    "Error marking '{}' as {}: {}",
+                    path.display(),
+                    action_name,
+                    e
+                );
+                false
+            }
+        }
+    };
+
     for pattern_str in file_patterns {
+        let kind = parse_pattern_kind(&pattern_str);
         let mut pattern_matched_at_least_one_file = false;
-        // Try glob expansion first
-        match glob(&pattern_str) {
-            Ok(paths) => {
-                for entry in paths {
-                    match entry {
-                        Ok(path) => {
+
+        match kind {
+            PatternKind::Glob(pattern) => match Regex::new(&glob_to_regex(&pattern)) {
+                Ok(re) => {
+                    let root = proj
+                        .get_config_as_ref()
+                        .get_src_dir_path()
+                        .unwrap_or_else(|| proj.get_root_path());
+                    for relative in walk_relative_files(proj, &root) {
+                        if re.is_match(&relative.to_string_lossy()) {
                             pattern_matched_at_least_one_file = true;
-                            match action_fn(proj, path.clone()) {
-                                Ok(_) => {
-                                    println!(
-                                        "Successfully marked '{}' as {}.",
-                                        path.display(),
-                                        action_name
-                                    );
-                                    success_count += 1;
-                                }
-                                Err(e) => {
-                                    eprintln!(
-                                        "Error marking '{}' as {}: {}",
-                                        path.display(),
-                                        action_name,
-                                        e
-                                    );
-                                    error_count += 1;
-                                }
+                            if mark_one(root.join(&relative), proj) {
+                                success_count += 1;
+                            } else {
+                                error_count += 1;
                             }
                         }
-                        Err(e) => {
-                            eprintln!(
-                                "Error processing glob entry for pattern '{}': {}",
-                                pattern_str, e
-                            );
-                            error_count += 1;
-                        }
                     }
                 }
-            }
-            Err(e) => {
-                // This means the pattern itself is invalid, not that it didn't match.
-                eprintln!("Invalid glob pattern '{}': {}", pattern_str, e);
-                error_count += 1;
-                continue; // Skip to next pattern
-            }
-        }
-
-        // If glob didn't match anything AND the pattern doesn't look like a typical glob,
-        // try treating it as a literal path.
-        if !pattern_matched_at_least_one_file
-            && !pattern_str.contains('*')
-            && !pattern_str.contains('?')
-            && !pattern_str.contains('[')
-            && !pattern_str.contains('{')
-        {
-            let path = PathBuf::from(&pattern_str);
-            match action_fn(proj, path.clone()) {
-                Ok(_) => {
-                    println!(
-                        "Successfully marked '{}' as {}.",
-                        path.display(),
-                        action_name
-                    );
-                    success_count += 1;
+                Err(e) => {
+                    eprintln!("Invalid glob pattern '{}': {}", pattern_str, e);
+                    error_count += 1;
+                    continue; // Skip to next pattern
+                }
+            },
+            PatternKind::Regex(pattern) => match Regex::new(&pattern) {
+                Ok(re) => {
+                    let root = proj
+                        .get_config_as_ref()
+                        .get_src_dir_path()
+                        .unwrap_or_else(|| proj.get_root_path());
+                    for relative in walk_relative_files(proj, &root) {
+                        if re.is_match(&relative.to_string_lossy()) {
+                            pattern_matched_at_least_one_file = true;
+                            if mark_one(root.join(&relative), proj) {
+                                success_count += 1;
+                            } else {
+                                error_count += 1;
+                            }
+                        }
+                    }
                 }
                 Err(e) => {
-                    // If this also fails, it's likely a "NoFile" error, which is fine to report
-                    // as "no match" if it was intended as a literal.
-                    eprintln!(
-                        "Error marking literal path '{}' as {}: {}",
-                        path.display(),
-                        action_name,
-                        e
-                    );
-                    error_count += 1; // Count as error if specific file not found
-                                      // Or, if we want to be more lenient for literal paths that don't exist:
-                                      // no_match_patterns.push(pattern_str);
+                    eprintln!("Invalid regex pattern '{}': {}", pattern_str, e);
+                    error_count += 1;
+                    continue; // Skip to next pattern
+                }
+            },
+            PatternKind::Path(pattern) => {
+                pattern_matched_at_least_one_file = true;
+                if mark_one(PathBuf::from(&pattern), proj) {
+                    success_count += 1;
+                } else {
+                    error_count += 1;
                 }
             }
-        } else if !pattern_matched_at_least_one_file {
+        }
+
+        if !pattern_matched_at_least_one_file {
             no_match_patterns.push(pattern_str);
         }
     }
@@ -233,18 +331,23 @@ fn handle_project_action(
         action,
         ProjectAction::TranslateFile { .. } | ProjectAction::TranslateAll { .. }
     ) {
-        if std::env::var("GOOGLE_API_KEY").is_err() {
-            eprintln!("Error: The GOOGLE_API_KEY environment variable must be set to use translation features.");
+        let env_var = proj.get_backend_env_var_name();
+        if std::env::var(env_var).is_err() {
+            eprintln!(
+                "Error: The {} environment variable must be set to use the configured translation backend.",
+                env_var
+            );
             exit(1);
         }
     }
 
     match action {
         ProjectAction::SetSource { dir_name, language } => {
+            let lang_for_print = language.clone();
             match proj.set_source_dir(&dir_name, language) {
                 Ok(_) => println!(
-                    "Successfully set source directory to '{}' with language {:?}",
-                    dir_name, language
+                    "Successfully set source directory to '{}' with language {}",
+                    dir_name, lang_for_print
                 ),
                 Err(e) => {
                     eprintln!("Error setting source directory: {}", e);
@@ -252,20 +355,26 @@ fn handle_project_action(
                 }
             }
         }
-        ProjectAction::AddTargetLang { language } => match proj.add_lang(language) {
-            Ok(_) => println!("Successfully added target language {:?}", language),
-            Err(e) => {
-                eprintln!("Error adding target language: {}", e);
-                exit(1);
+        ProjectAction::AddTargetLang { language } => {
+            let lang_for_print = language.clone();
+            match proj.add_lang(language) {
+                Ok(_) => println!("Successfully added target language {}", lang_for_print),
+                Err(e) => {
+                    eprintln!("Error adding target language: {}", e);
+                    exit(1);
+                }
             }
-        },
-        ProjectAction::RemoveTargetLang { language } => match proj.remove_lang(language) {
-            Ok(_) => println!("Successfully removed target language {:?}", language),
-            Err(e) => {
-                eprintln!("Error removing target language: {}", e);
-                exit(1);
+        }
+        ProjectAction::RemoveTargetLang { language } => {
+            let lang_for_print = language.clone();
+            match proj.remove_lang(language) {
+                Ok(_) => println!("Successfully removed target language {}", lang_for_print),
+                Err(e) => {
+                    eprintln!("Error removing target language: {}", e);
+                    exit(1);
+                }
             }
-        },
+        }
         ProjectAction::Sync => match proj.sync_files() {
             Ok(_) => println!("Successfully synced untranslatable files."),
             Err(e) => {
@@ -273,13 +382,34 @@ fn handle_project_action(
                 exit(1);
             }
         },
-        ProjectAction::MarkTranslatable { file_patterns } => {
-            process_file_patterns(
-                proj,
-                file_patterns,
-                |p, path| p.make_translatable_file(path),
-                "translatable",
-            );
+        ProjectAction::MarkTranslatable {
+            file_patterns,
+            extension,
+        } => {
+            if file_patterns.is_empty() && extension.is_empty() {
+                eprintln!("Error: provide at least one file pattern or --extension filter.");
+                exit(1);
+            }
+            if !extension.is_empty() {
+                match proj.mark_translatable_by_extensions(&extension) {
+                    Ok(marked) => println!(
+                        "Successfully marked {} file(s) matching extension filter {:?} as translatable.",
+                        marked, extension
+                    ),
+                    Err(e) => {
+                        eprintln!("Error marking files by extension: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+            if !file_patterns.is_empty() {
+                process_file_patterns(
+                    proj,
+                    file_patterns,
+                    |p, path| p.make_translatable_file(path),
+                    "translatable",
+                );
+            }
         }
         ProjectAction::MarkUntranslatable { file_patterns } => {
             process_file_patterns(
@@ -289,7 +419,11 @@ fn handle_project_action(
                 "untranslatable",
             );
         }
-        ProjectAction::ListTranslatable => match proj.get_translatable_files() {
+        ProjectAction::ListTranslatable { extension } => match if extension.is_empty() {
+            proj.get_translatable_files()
+        } else {
+            proj.get_translatable_files_filtered(&extension)
+        } {
             Ok(files) => {
                 if files.is_empty() {
                     println!("No translatable files found.");
@@ -308,16 +442,22 @@ fn handle_project_action(
         ProjectAction::TranslateFile {
             file_path,
             target_language,
+            force,
         } => {
-            let lang_for_print = target_language;
+            let lang_for_print = target_language.clone();
             println!(
-                "Translating '{}' to {:?}...",
+                "Translating '{}' to {}...",
                 file_path.display(),
                 lang_for_print
             );
-            match proj.translate_file(file_path.clone(), target_language) {
-                Ok(_) => println!(
-                    "Successfully submitted '{}' for translation to {:?}.",
+            match proj.translate_file(file_path.clone(), target_language, force) {
+                Ok(translate_dir_lib::cache::TranslationOutcome::UpToDate) => println!(
+                    "'{}' is already up to date for {}; skipping (use --force to override).",
+                    file_path.display(),
+                    lang_for_print
+                ),
+                Ok(translate_dir_lib::cache::TranslationOutcome::Translated) => println!(
+                    "Successfully submitted '{}' for translation to {}.",
                     file_path.display(),
                     lang_for_print
                 ),
@@ -327,13 +467,16 @@ fn handle_project_action(
                 }
             }
         }
-        ProjectAction::TranslateAll { target_language } => {
-            let lang_for_print = target_language;
-            println!("Translating all files to {:?}...", lang_for_print);
-            match proj.translate_all(target_language) {
-                Ok(_) => println!(
-                    "Successfully submitted all translatable files for translation to {:?}.",
-                    lang_for_print
+        ProjectAction::TranslateAll {
+            target_language,
+            force,
+        } => {
+            let lang_for_print = target_language.clone();
+            println!("Translating all files to {}...", lang_for_print);
+            match proj.translate_all(target_language, force) {
+                Ok(summary) => println!(
+                    "Submitted {} file(s) for translation to {} ({} already up to date).",
+                    summary.translated, lang_for_print, summary.up_to_date
                 ),
                 Err(e) => {
                     eprintln!("Error translating all files: {}", e);
@@ -341,6 +484,52 @@ fn handle_project_action(
                 }
             }
         }
+        ProjectAction::Status => match proj.status() {
+            Ok(entries) => {
+                if entries.is_empty() {
+                    println!("No translatable files found.");
+                } else {
+                    for entry in entries {
+                        println!(
+                            "  {} [{}]: {}",
+                            entry.file.display(),
+                            entry.target_language,
+                            entry.state
+                        );
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("Error computing translation status: {}", e);
+                exit(1);
+            }
+        },
+        ProjectAction::SetBackend {
+            engine,
+            api_key_env,
+            base_url,
+            glossary,
+        } => {
+            let engine_name = match engine {
+                BackendKind::Google => "google",
+                BackendKind::Deepl => "deepl",
+                BackendKind::Http => "http",
+            };
+            match proj.set_backend(engine_name, api_key_env, base_url, glossary) {
+                Ok(_) => println!("Successfully set translation backend to '{}'.", engine_name),
+                Err(e) => {
+                    eprintln!("Error setting translation backend: {}", e);
+                    exit(1);
+                }
+            }
+        }
+        ProjectAction::SetLayout { layout } => match proj.set_output_layout(layout.as_str()) {
+            Ok(_) => println!("Successfully set output layout to '{}'.", layout.as_str()),
+            Err(e) => {
+                eprintln!("Error setting output layout: {}", e);
+                exit(1);
+            }
+        },
         ProjectAction::Info => {
             display_project_info(proj.get_config_as_ref(), proj.get_root_path());
         }
@@ -353,7 +542,7 @@ fn display_project_info(config: &ProjectConfig, root_path: PathBuf) {
     println!("  Project Name: {}", config.get_name());
 
     if let Some(src_dir_lang) = config.get_src_dir_as_ref() {
-        println!("  Source Language: {:?}", src_dir_lang.get_lang());
+        println!("  Source Language: {}", src_dir_lang.get_lang());
         if let Some(src_path) = config.get_src_dir_path() {
             println!("  Source Directory: {}", src_path.display());
         } else {
@@ -370,10 +559,17 @@ fn display_project_info(config: &ProjectConfig, root_path: PathBuf) {
         println!("  Target Languages:");
         for lang_dir in target_langs {
             println!(
-                "    - {:?}: {}",
+                "    - {}: {}",
                 lang_dir.get_lang(),
                 lang_dir.get_dir_as_ref().get_path().display()
             );
         }
     }
+
+    println!("  Backend: {}", config.get_backend_name());
+    println!(
+        "  Backend API Key Env: {}",
+        config.get_backend_env_var_name()
+    );
+    println!("  Output Layout: {}", config.get_output_layout_name());
 }
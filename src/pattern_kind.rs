@@ -0,0 +1,159 @@
+/// How a `MarkTranslatable`/`MarkUntranslatable` argument should be matched
+/// against the source tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternKind {
+    /// `glob:` prefix, or no recognized prefix and the text looks like a glob.
+    Glob(String),
+    /// `re:` prefix: a regular expression matched against relative paths.
+    Regex(String),
+    /// `path:` prefix, or no recognized prefix and the text looks like a literal path.
+    Path(String),
+}
+
+/// Splits a recognized `glob:`/`re:`/`path:` prefix off `raw` and returns the
+/// matching mode together with the remaining pattern text. Falls back to the
+/// existing glob-then-literal heuristic when no prefix is present.
+pub fn parse_pattern_kind(raw: &str) -> PatternKind {
+    if let Some(rest) = raw.strip_prefix("glob:") {
+        PatternKind::Glob(rest.to_string())
+    } else if let Some(rest) = raw.strip_prefix("re:") {
+        PatternKind::Regex(rest.to_string())
+    } else if let Some(rest) = raw.strip_prefix("path:") {
+        PatternKind::Path(rest.to_string())
+    } else if raw.contains(['*', '?', '[', '{']) {
+        PatternKind::Glob(raw.to_string())
+    } else {
+        PatternKind::Path(raw.to_string())
+    }
+}
+
+/// Translates a glob pattern into an anchored regex, escaping regex
+/// metacharacters in literal runs first so that e.g. `.` in `page.md` is
+/// matched literally.
+///
+/// Recognized glob syntax: `**/` (any number of directories, including
+/// none), `**` (anything, across directory boundaries), `*` (anything but a
+/// path separator), `?` (any single character, a path separator included), and `[...]`
+/// character classes, which pass straight through into the regex.
+pub fn glob_to_regex(glob: &str) -> String {
+    let mut out = String::from("^");
+    let chars: Vec<char> = glob.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars[i..].starts_with(&['*', '*', '/']) => {
+                out.push_str("(?:.*/)?");
+                i += 3;
+            }
+            '*' if chars[i..].starts_with(&['*', '*']) => {
+                out.push_str(".*");
+                i += 2;
+            }
+            '*' => {
+                out.push_str("[^/]*");
+                i += 1;
+            }
+            '?' => {
+                out.push('.');
+                i += 1;
+            }
+            '[' => {
+                // Pass the character class through verbatim.
+                let start = i;
+                while i < chars.len() && chars[i] != ']' {
+                    i += 1;
+                }
+                if i < chars.len() {
+                    i += 1; // include the closing ']'
+                }
+                out.extend(&chars[start..i]);
+            }
+            c => {
+                if regex_syntax::is_meta_character(c) {
+                    out.push('\\');
+                }
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out.push('$');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pattern_kind_recognizes_prefixes() {
+        assert_eq!(
+            parse_pattern_kind("glob:*.md"),
+            PatternKind::Glob("*.md".to_string())
+        );
+        assert_eq!(
+            parse_pattern_kind("re:.*\\.md$"),
+            PatternKind::Regex(".*\\.md$".to_string())
+        );
+        assert_eq!(
+            parse_pattern_kind("path:weird[name].txt"),
+            PatternKind::Path("weird[name].txt".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_pattern_kind_falls_back_to_the_glob_then_literal_heuristic() {
+        assert_eq!(
+            parse_pattern_kind("docs/*.md"),
+            PatternKind::Glob("docs/*.md".to_string())
+        );
+        assert_eq!(
+            parse_pattern_kind("docs/page.md"),
+            PatternKind::Path("docs/page.md".to_string())
+        );
+    }
+
+    fn matches(glob: &str, candidate: &str) -> bool {
+        regex::Regex::new(&glob_to_regex(glob))
+            .unwrap()
+            .is_match(candidate)
+    }
+
+    #[test]
+    fn star_does_not_cross_directory_boundaries() {
+        assert!(matches("*.md", "page.md"));
+        assert!(!matches("*.md", "docs/page.md"));
+    }
+
+    #[test]
+    fn double_star_crosses_directory_boundaries() {
+        assert!(matches("**.md", "docs/nested/page.md"));
+        assert!(matches("**.md", "page.md"));
+    }
+
+    #[test]
+    fn double_star_slash_matches_zero_or_more_directories() {
+        assert!(matches("**/page.md", "page.md"));
+        assert!(matches("**/page.md", "docs/nested/page.md"));
+        assert!(!matches("**/page.md", "page.txt"));
+    }
+
+    #[test]
+    fn question_mark_matches_a_single_character() {
+        assert!(matches("page?.md", "page1.md"));
+        assert!(!matches("page?.md", "page12.md"));
+    }
+
+    #[test]
+    fn character_classes_pass_through_verbatim() {
+        assert!(matches("page[12].md", "page1.md"));
+        assert!(!matches("page[12].md", "page3.md"));
+    }
+
+    #[test]
+    fn literal_dots_are_escaped() {
+        // Without escaping, "." would match any character, e.g. "pagexmd".
+        assert!(!matches("page.md", "pagexmd"));
+        assert!(matches("page.md", "page.md"));
+    }
+}
@@ -0,0 +1,86 @@
+//! End-to-end coverage of the init/set-source/add-lang/mark/translate flow
+//! against a throwaway project, for both the mirrored-directory and
+//! language-suffix output layouts.
+
+use translate_dir_lib::backend::StubBackend;
+use translate_dir_lib::project;
+
+/// Asserts that `$path` exists on disk.
+macro_rules! file_exists {
+    ($path:expr) => {
+        assert!(
+            std::path::Path::new(&$path).exists(),
+            "expected '{}' to exist",
+            $path.display()
+        )
+    };
+}
+
+/// Asserts that the file at `$path` contains `$needle`.
+macro_rules! file_contains {
+    ($path:expr, $needle:expr) => {{
+        let contents = std::fs::read_to_string(&$path)
+            .unwrap_or_else(|e| panic!("could not read '{}': {}", $path.display(), e));
+        assert!(
+            contents.contains($needle),
+            "expected '{}' to contain '{}', got:\n{}",
+            $path.display(),
+            $needle,
+            contents
+        )
+    }};
+}
+
+fn build_project(dir: &std::path::Path, layout: &str) -> project::Project {
+    project::init("demo", dir.to_path_buf(), layout).expect("project::init should succeed");
+    let src_dir = dir.join("src_docs");
+    std::fs::create_dir_all(&src_dir).unwrap();
+    std::fs::write(src_dir.join("page.md"), "# Hello\n").unwrap();
+
+    let mut proj = project::load(dir.to_path_buf()).expect("project::load should succeed");
+    let en: unic_langid::LanguageIdentifier = "en".parse().unwrap();
+    let fr: unic_langid::LanguageIdentifier = "fr".parse().unwrap();
+    proj.set_source_dir("src_docs", en).unwrap();
+    proj.add_lang(fr).unwrap();
+    proj.make_translatable_file(src_dir.join("page.md"))
+        .unwrap();
+    proj.set_backend_for_test(Box::new(StubBackend::echo()));
+    proj
+}
+
+#[test]
+fn mirrored_layout_writes_a_per_language_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut proj = build_project(dir.path(), "mirrored");
+    let fr: unic_langid::LanguageIdentifier = "fr".parse().unwrap();
+
+    proj.translate_all(fr, false).unwrap();
+
+    let translated = dir.path().join("fr").join("page.md");
+    file_exists!(translated);
+    file_contains!(translated, "Hello");
+}
+
+#[test]
+fn suffix_layout_writes_next_to_the_source_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut proj = build_project(dir.path(), "suffix");
+    let fr: unic_langid::LanguageIdentifier = "fr".parse().unwrap();
+
+    proj.translate_all(fr, false).unwrap();
+
+    let translated = dir.path().join("src_docs").join("page.fr.md");
+    file_exists!(translated);
+    file_contains!(translated, "Hello");
+
+    // Re-loading the project and re-running extension-marking must not pick
+    // up `page.fr.md` as a new source file.
+    let mut reloaded = project::load(dir.path().to_path_buf()).unwrap();
+    let marked = reloaded
+        .mark_translatable_by_extensions(&["md".to_string()])
+        .unwrap();
+    assert_eq!(marked, 0, "page.fr.md should not be marked translatable");
+
+    let sources = reloaded.get_translatable_files().unwrap();
+    assert!(!sources.iter().any(|p| p.ends_with("page.fr.md")));
+}
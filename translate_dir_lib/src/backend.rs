@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use unic_langid::LanguageIdentifier;
+
+/// A translation engine a project can be configured to use.
+pub trait Backend {
+    fn translate(
+        &self,
+        text: &str,
+        source_lang: &LanguageIdentifier,
+        target_lang: &LanguageIdentifier,
+    ) -> Result<String, BackendError>;
+}
+
+#[derive(Debug, Error)]
+pub enum BackendError {
+    #[error("unknown translation engine '{0}'")]
+    UnknownEngine(String),
+    #[error("the 'http' engine requires --base-url")]
+    MissingBaseUrl,
+    #[error("the {0} environment variable is not set")]
+    MissingApiKey(String),
+    #[error("request to translation backend failed: {0}")]
+    RequestFailed(String),
+}
+
+/// The persisted `[backend]` section of a project config: which engine is
+/// selected plus its engine-specific fields.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BackendConfig {
+    engine: String,
+    api_key_env: String,
+    base_url: Option<String>,
+    glossary: Option<String>,
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            engine: "google".to_string(),
+            api_key_env: default_env_var("google"),
+            base_url: None,
+            glossary: None,
+        }
+    }
+}
+
+impl BackendConfig {
+    /// Validates `engine` and its accompanying fields, filling in the
+    /// engine's default API-key env var when `api_key_env` is omitted.
+    pub fn new(
+        engine: &str,
+        api_key_env: Option<String>,
+        base_url: Option<String>,
+        glossary: Option<String>,
+    ) -> Result<Self, BackendError> {
+        match engine {
+            "google" | "deepl" => {}
+            "http" => {
+                if base_url.is_none() {
+                    return Err(BackendError::MissingBaseUrl);
+                }
+            }
+            other => return Err(BackendError::UnknownEngine(other.to_string())),
+        }
+        Ok(Self {
+            api_key_env: api_key_env.unwrap_or_else(|| default_env_var(engine)),
+            engine: engine.to_string(),
+            base_url,
+            glossary,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.engine
+    }
+
+    pub fn env_var_name(&self) -> &str {
+        &self.api_key_env
+    }
+
+    /// Builds the live `Backend` this config describes.
+    pub fn build(&self) -> Box<dyn Backend> {
+        match self.engine.as_str() {
+            "deepl" => Box::new(DeeplBackend {
+                api_key_env: self.api_key_env.clone(),
+                glossary: self.glossary.clone(),
+            }),
+            "http" => Box::new(HttpBackend {
+                base_url: self.base_url.clone().unwrap_or_default(),
+                api_key_env: self.api_key_env.clone(),
+                glossary: self.glossary.clone(),
+            }),
+            _ => Box::new(GoogleBackend {
+                api_key_env: self.api_key_env.clone(),
+                glossary: self.glossary.clone(),
+            }),
+        }
+    }
+}
+
+fn default_env_var(engine: &str) -> String {
+    match engine {
+        "deepl" => "DEEPL_API_KEY",
+        "http" => "TRANSLATE_API_KEY",
+        _ => "GOOGLE_API_KEY",
+    }
+    .to_string()
+}
+
+pub struct GoogleBackend {
+    api_key_env: String,
+    glossary: Option<String>,
+}
+
+impl Backend for GoogleBackend {
+    fn translate(
+        &self,
+        text: &str,
+        _source_lang: &LanguageIdentifier,
+        target_lang: &LanguageIdentifier,
+    ) -> Result<String, BackendError> {
+        let api_key = std::env::var(&self.api_key_env)
+            .map_err(|_| BackendError::MissingApiKey(self.api_key_env.clone()))?;
+        let url = format!(
+            "https://translation.googleapis.com/language/translate/v2?key={}",
+            api_key
+        );
+        let mut body = serde_json::json!({
+            "q": text,
+            "target": target_lang.to_string(),
+            "format": "text",
+        });
+        if let Some(glossary) = &self.glossary {
+            body["glossaryConfig"] = serde_json::json!({ "glossary": glossary });
+        }
+        let resp = ureq::post(&url)
+            .send_json(body)
+            .map_err(|e| BackendError::RequestFailed(e.to_string()))?;
+        let body: serde_json::Value = resp
+            .into_json()
+            .map_err(|e| BackendError::RequestFailed(e.to_string()))?;
+        body["data"]["translations"][0]["translatedText"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| BackendError::RequestFailed("unexpected response shape".to_string()))
+    }
+}
+
+pub struct DeeplBackend {
+    api_key_env: String,
+    glossary: Option<String>,
+}
+
+impl Backend for DeeplBackend {
+    fn translate(
+        &self,
+        text: &str,
+        source_lang: &LanguageIdentifier,
+        target_lang: &LanguageIdentifier,
+    ) -> Result<String, BackendError> {
+        let api_key = std::env::var(&self.api_key_env)
+            .map_err(|_| BackendError::MissingApiKey(self.api_key_env.clone()))?;
+        let mut body = serde_json::json!({
+            "text": [text],
+            "source_lang": source_lang.language.to_string().to_uppercase(),
+            "target_lang": target_lang.language.to_string().to_uppercase(),
+        });
+        if let Some(glossary) = &self.glossary {
+            body["glossary_id"] = serde_json::json!(glossary);
+        }
+        let resp = ureq::post("https://api-free.deepl.com/v2/translate")
+            .set("Authorization", &format!("DeepL-Auth-Key {}", api_key))
+            .send_json(body)
+            .map_err(|e| BackendError::RequestFailed(e.to_string()))?;
+        let body: serde_json::Value = resp
+            .into_json()
+            .map_err(|e| BackendError::RequestFailed(e.to_string()))?;
+        body["translations"][0]["text"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| BackendError::RequestFailed("unexpected response shape".to_string()))
+    }
+}
+
+/// A generic engine for self-hosted or third-party HTTP translation
+/// endpoints: posts `{text, source, target, glossary}` and expects back
+/// `{"translated_text": "..."}`.
+pub struct HttpBackend {
+    base_url: String,
+    api_key_env: String,
+    glossary: Option<String>,
+}
+
+impl Backend for HttpBackend {
+    fn translate(
+        &self,
+        text: &str,
+        source_lang: &LanguageIdentifier,
+        target_lang: &LanguageIdentifier,
+    ) -> Result<String, BackendError> {
+        let mut request = ureq::post(&self.base_url);
+        if let Ok(api_key) = std::env::var(&self.api_key_env) {
+            request = request.set("Authorization", &format!("Bearer {}", api_key));
+        }
+        let resp = request
+            .send_json(serde_json::json!({
+                "text": text,
+                "source": source_lang.to_string(),
+                "target": target_lang.to_string(),
+                "glossary": self.glossary,
+            }))
+            .map_err(|e| BackendError::RequestFailed(e.to_string()))?;
+        let body: serde_json::Value = resp
+            .into_json()
+            .map_err(|e| BackendError::RequestFailed(e.to_string()))?;
+        body["translated_text"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| BackendError::RequestFailed("unexpected response shape".to_string()))
+    }
+}
+
+/// A backend that never makes a network call, for use in tests.
+pub struct StubBackend {
+    echo: bool,
+}
+
+impl StubBackend {
+    /// Returns the source text unchanged, so tests can assert on it without
+    /// depending on a real translation result.
+    pub fn echo() -> Self {
+        Self { echo: true }
+    }
+}
+
+impl Backend for StubBackend {
+    fn translate(
+        &self,
+        text: &str,
+        _source_lang: &LanguageIdentifier,
+        _target_lang: &LanguageIdentifier,
+    ) -> Result<String, BackendError> {
+        Ok(if self.echo {
+            text.to_string()
+        } else {
+            String::new()
+        })
+    }
+}
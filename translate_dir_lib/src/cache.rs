@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use unic_langid::LanguageIdentifier;
+
+const CACHE_FILE_NAME: &str = "translation_cache.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CacheEntry {
+    file: PathBuf,
+    lang: String,
+    source_hash: String,
+}
+
+/// A per-`(source_file, target_language)` record of the source-content hash
+/// that produced the translation currently on disk, so repeated runs can
+/// skip files that haven't changed.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct TranslationCache {
+    entries: Vec<CacheEntry>,
+}
+
+impl TranslationCache {
+    pub fn load(config_dir: &Path) -> std::io::Result<Self> {
+        let path = config_dir.join(CACHE_FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents).unwrap_or_default())
+    }
+
+    pub fn save(&self, config_dir: &Path) -> std::io::Result<()> {
+        let path = config_dir.join(CACHE_FILE_NAME);
+        let contents = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, contents)
+    }
+
+    pub fn get(&self, file: &Path, lang: &LanguageIdentifier) -> Option<&str> {
+        let lang = lang.to_string();
+        self.entries
+            .iter()
+            .find(|e| e.file == file && e.lang == lang)
+            .map(|e| e.source_hash.as_str())
+    }
+
+    pub fn set(&mut self, file: PathBuf, lang: &LanguageIdentifier, source_hash: String) {
+        let lang = lang.to_string();
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.file == file && e.lang == lang)
+        {
+            entry.source_hash = source_hash;
+        } else {
+            self.entries.push(CacheEntry {
+                file,
+                lang,
+                source_hash,
+            });
+        }
+    }
+}
+
+/// Hex-encoded SHA-256 digest of a file's contents.
+pub fn hash_file(path: &Path) -> std::io::Result<String> {
+    let contents = std::fs::read(path)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&contents);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// What happened when a file was submitted for translation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationOutcome {
+    Translated,
+    UpToDate,
+}
+
+/// Aggregate result of translating every translatable file for one target
+/// language.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TranslateAllSummary {
+    pub translated: usize,
+    pub up_to_date: usize,
+}
+
+/// The state of one `(file, target_language)` pair, as reported by `Status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationState {
+    UpToDate,
+    Stale,
+    Missing,
+}
+
+impl fmt::Display for TranslationState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            TranslationState::UpToDate => "up to date",
+            TranslationState::Stale => "stale",
+            TranslationState::Missing => "missing",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// One row of a `Status` report.
+#[derive(Debug, Clone)]
+pub struct StatusEntry {
+    pub file: PathBuf,
+    pub target_language: LanguageIdentifier,
+    pub state: TranslationState,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lang(tag: &str) -> LanguageIdentifier {
+        tag.parse().unwrap()
+    }
+
+    #[test]
+    fn hash_file_is_deterministic_and_content_sensitive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("page.md");
+        std::fs::write(&path, "Hello").unwrap();
+        let first = hash_file(&path).unwrap();
+        let second = hash_file(&path).unwrap();
+        assert_eq!(first, second);
+
+        std::fs::write(&path, "Hello, world").unwrap();
+        let third = hash_file(&path).unwrap();
+        assert_ne!(first, third);
+    }
+
+    #[test]
+    fn get_returns_none_for_an_unseen_file() {
+        let cache = TranslationCache::default();
+        assert_eq!(cache.get(Path::new("page.md"), &lang("fr")), None);
+    }
+
+    #[test]
+    fn set_then_get_round_trips_the_hash() {
+        let mut cache = TranslationCache::default();
+        cache.set(PathBuf::from("page.md"), &lang("fr"), "abc123".to_string());
+        assert_eq!(cache.get(Path::new("page.md"), &lang("fr")), Some("abc123"));
+    }
+
+    #[test]
+    fn set_updates_the_existing_entry_instead_of_duplicating_it() {
+        let mut cache = TranslationCache::default();
+        cache.set(PathBuf::from("page.md"), &lang("fr"), "abc123".to_string());
+        cache.set(PathBuf::from("page.md"), &lang("fr"), "def456".to_string());
+        assert_eq!(cache.get(Path::new("page.md"), &lang("fr")), Some("def456"));
+        assert_eq!(cache.entries.len(), 1);
+    }
+
+    #[test]
+    fn entries_are_scoped_per_target_language() {
+        let mut cache = TranslationCache::default();
+        cache.set(PathBuf::from("page.md"), &lang("fr"), "abc123".to_string());
+        assert_eq!(cache.get(Path::new("page.md"), &lang("de")), None);
+    }
+
+    #[test]
+    fn save_then_load_round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = TranslationCache::default();
+        cache.set(PathBuf::from("page.md"), &lang("fr"), "abc123".to_string());
+        cache.save(dir.path()).unwrap();
+
+        let reloaded = TranslationCache::load(dir.path()).unwrap();
+        assert_eq!(reloaded.get(Path::new("page.md"), &lang("fr")), Some("abc123"));
+    }
+
+    #[test]
+    fn load_with_no_cache_file_returns_an_empty_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = TranslationCache::load(dir.path()).unwrap();
+        assert_eq!(cache.get(Path::new("page.md"), &lang("fr")), None);
+    }
+}
@@ -0,0 +1,128 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InitError {
+    #[error("could not create '{path}': {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to write project config: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("'{0}' is not a recognized output layout")]
+    InvalidLayout(String),
+}
+
+#[derive(Debug, Error)]
+pub enum LoadError {
+    #[error("'{0}' does not look like a translate-dir project (no config found)")]
+    NotAProject(PathBuf),
+    #[error("could not read project config: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse project config: {0}")]
+    Deserialize(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SetSourceError {
+    #[error("'{0}' is not a directory")]
+    NotADirectory(PathBuf),
+    #[error("could not persist project config: {0}")]
+    Save(#[from] SaveError),
+}
+
+#[derive(Debug, Error)]
+pub enum AddLangError {
+    #[error("target language '{0}' is already configured")]
+    AlreadyPresent(String),
+    #[error("could not persist project config: {0}")]
+    Save(#[from] SaveError),
+}
+
+#[derive(Debug, Error)]
+pub enum RemoveLangError {
+    #[error("target language '{0}' is not configured")]
+    NotPresent(String),
+    #[error("could not persist project config: {0}")]
+    Save(#[from] SaveError),
+}
+
+#[derive(Debug, Error)]
+pub enum AddTranslatableFileError {
+    #[error("'{0}' does not exist")]
+    NoFile(PathBuf),
+    #[error("'{0}' is not inside the source directory")]
+    NotInSourceDir(PathBuf),
+    #[error("no source directory has been set for this project")]
+    NoSourceDir,
+    #[error("'{0}' is not marked as translatable")]
+    NotTranslatable(PathBuf),
+    #[error("could not persist project config: {0}")]
+    Save(#[from] SaveError),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SyncError {
+    #[error("no source directory has been set for this project")]
+    NoSourceDir,
+    #[error("I/O error while syncing files: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ListError {
+    #[error("no source directory has been set for this project")]
+    NoSourceDir,
+    #[error("I/O error while listing translatable files: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum TranslateError {
+    #[error("no source directory has been set for this project")]
+    NoSourceDir,
+    #[error("'{0}' is not marked as translatable")]
+    NotTranslatable(PathBuf),
+    #[error("'{0}' is not a configured target language")]
+    UnknownTargetLang(String),
+    #[error("I/O error while translating: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Backend(#[from] crate::backend::BackendError),
+}
+
+#[derive(Debug, Error)]
+pub enum StatusError {
+    #[error("no source directory has been set for this project")]
+    NoSourceDir,
+    #[error("I/O error while computing status: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SetBackendError {
+    #[error(transparent)]
+    Backend(#[from] crate::backend::BackendError),
+    #[error("could not persist project config: {0}")]
+    Save(#[from] SaveError),
+}
+
+#[derive(Debug, Error)]
+pub enum SetLayoutError {
+    #[error("'{0}' is not a recognized output layout")]
+    UnknownLayout(String),
+    #[error("could not persist project config: {0}")]
+    Save(#[from] SaveError),
+}
+
+#[derive(Debug, Error)]
+#[error("could not write project config to '{path}': {source}")]
+pub struct SaveError {
+    pub path: PathBuf,
+    #[source]
+    pub source: std::io::Error,
+}
@@ -0,0 +1,252 @@
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// A compiled `.translateignore` file: gitignore-style globs, with leading
+/// `!` re-inclusion, `/`-anchored patterns, and directory patterns. Rules are
+/// evaluated in order, last match wins, matching gitignore semantics.
+pub struct IgnoreMatcher {
+    rules: Vec<(Regex, bool)>,
+}
+
+impl IgnoreMatcher {
+    /// An empty matcher that ignores nothing, used when no `.translateignore`
+    /// file is present.
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    pub fn load(root: &Path) -> std::io::Result<Self> {
+        let ignore_path = root.join(".translateignore");
+        if !ignore_path.exists() {
+            return Ok(Self::empty());
+        }
+        let contents = std::fs::read_to_string(ignore_path)?;
+        let mut rules = Vec::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negate, pattern) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            if let Some(regex) = compile_pattern(pattern) {
+                rules.push((regex, negate));
+            }
+        }
+        Ok(Self { rules })
+    }
+
+    /// Whether `relative_path` (relative to the project's source directory)
+    /// is ignored.
+    pub fn is_ignored(&self, relative_path: &Path) -> bool {
+        let text = relative_path.to_string_lossy().replace('\\', "/");
+        let mut ignored = false;
+        for (regex, negate) in &self.rules {
+            if regex.is_match(&text) {
+                ignored = !negate;
+            }
+        }
+        ignored
+    }
+}
+
+fn compile_pattern(pattern: &str) -> Option<Regex> {
+    let anchored = pattern.starts_with('/');
+    let pattern = pattern.trim_start_matches('/');
+    let is_dir_pattern = pattern.ends_with('/');
+    let pattern = pattern.trim_end_matches('/');
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let body = glob_body_to_regex(pattern);
+    let suffix = if is_dir_pattern { "(?:/.*)?" } else { "" };
+    let full = if anchored {
+        format!("^{}{}$", body, suffix)
+    } else {
+        format!("^(?:.*/)?{}{}$", body, suffix)
+    };
+    Regex::new(&full).ok()
+}
+
+/// Translates gitignore-style glob syntax into the body of a regex (no
+/// anchors), escaping literal runs and expanding `**`, `*`, `?` and `[...]`.
+fn glob_body_to_regex(glob: &str) -> String {
+    let mut out = String::new();
+    let chars: Vec<char> = glob.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars[i..].starts_with(&['*', '*', '/']) => {
+                out.push_str("(?:.*/)?");
+                i += 3;
+            }
+            '*' if chars[i..].starts_with(&['*', '*']) => {
+                out.push_str(".*");
+                i += 2;
+            }
+            '*' => {
+                out.push_str("[^/]*");
+                i += 1;
+            }
+            '?' => {
+                out.push('.');
+                i += 1;
+            }
+            '[' => {
+                let start = i;
+                while i < chars.len() && chars[i] != ']' {
+                    i += 1;
+                }
+                if i < chars.len() {
+                    i += 1;
+                }
+                out.extend(&chars[start..i]);
+            }
+            c => {
+                if regex_syntax::is_meta_character(c) {
+                    out.push('\\');
+                }
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Expands `--extension`/`--type` tokens (bare extensions or known
+/// categories like "text"/"markdown") into a flat, lowercase, dot-free set
+/// of file extensions.
+pub fn expand_extension_filters(tokens: &[String]) -> Vec<String> {
+    let mut out = Vec::new();
+    for token in tokens {
+        let token = token.trim().trim_start_matches('.').to_lowercase();
+        match token.as_str() {
+            "text" => out.extend(["txt", "text"].map(String::from)),
+            "markdown" => out.extend(["md", "markdown"].map(String::from)),
+            "" => {}
+            other => out.push(other.to_string()),
+        }
+    }
+    out
+}
+
+pub fn matches_extension(path: &Path, extensions: &[String]) -> bool {
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    let ext = ext.to_lowercase();
+    extensions.iter().any(|allowed| allowed == &ext)
+}
+
+/// Recursively collects every file path under `root`, relative to `root`,
+/// skipping entries the `IgnoreMatcher` excludes and skipping `exclude`
+/// (the project's own config directory) wherever it falls under `root`.
+pub fn walk_files(root: &Path, ignore: &IgnoreMatcher, exclude: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path == exclude {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(root) else {
+                continue;
+            };
+            if ignore.is_ignored(relative) {
+                continue;
+            }
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                out.push(relative.to_path_buf());
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher_for(rules: &str) -> IgnoreMatcher {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".translateignore"), rules).unwrap();
+        IgnoreMatcher::load(dir.path()).unwrap()
+    }
+
+    #[test]
+    fn no_ignore_file_ignores_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let matcher = IgnoreMatcher::load(dir.path()).unwrap();
+        assert!(!matcher.is_ignored(Path::new("page.md")));
+    }
+
+    #[test]
+    fn blank_lines_and_comments_are_skipped() {
+        let matcher = matcher_for("\n# a comment\n\n*.log\n");
+        assert!(matcher.is_ignored(Path::new("debug.log")));
+        assert!(!matcher.is_ignored(Path::new("# a comment")));
+    }
+
+    #[test]
+    fn bare_pattern_matches_at_any_depth() {
+        let matcher = matcher_for("*.log");
+        assert!(matcher.is_ignored(Path::new("debug.log")));
+        assert!(matcher.is_ignored(Path::new("nested/dir/debug.log")));
+        assert!(!matcher.is_ignored(Path::new("debug.txt")));
+    }
+
+    #[test]
+    fn leading_slash_anchors_to_the_root() {
+        let matcher = matcher_for("/build.md");
+        assert!(matcher.is_ignored(Path::new("build.md")));
+        assert!(!matcher.is_ignored(Path::new("nested/build.md")));
+    }
+
+    #[test]
+    fn trailing_slash_ignores_the_whole_directory() {
+        let matcher = matcher_for("drafts/");
+        assert!(matcher.is_ignored(Path::new("drafts/page.md")));
+        assert!(matcher.is_ignored(Path::new("nested/drafts/page.md")));
+        assert!(!matcher.is_ignored(Path::new("drafts.md")));
+    }
+
+    #[test]
+    fn later_rules_override_earlier_ones() {
+        // gitignore semantics: last matching rule wins, so a later
+        // re-inclusion un-ignores a file an earlier rule ignored.
+        let matcher = matcher_for("*.md\n!keep.md\n");
+        assert!(matcher.is_ignored(Path::new("page.md")));
+        assert!(!matcher.is_ignored(Path::new("keep.md")));
+    }
+
+    #[test]
+    fn a_later_ignore_rule_can_re_ignore_a_negated_file() {
+        let matcher = matcher_for("*.md\n!keep.md\nkeep.md\n");
+        assert!(matcher.is_ignored(Path::new("keep.md")));
+    }
+
+    #[test]
+    fn expand_extension_filters_expands_known_categories() {
+        let tokens = vec!["Text".to_string(), ".MD".to_string()];
+        let mut expanded = expand_extension_filters(&tokens);
+        expanded.sort();
+        assert_eq!(expanded, vec!["md", "text", "txt"]);
+    }
+
+    #[test]
+    fn matches_extension_is_case_insensitive() {
+        let extensions = vec!["md".to_string()];
+        assert!(matches_extension(Path::new("page.MD"), &extensions));
+        assert!(!matches_extension(Path::new("page.txt"), &extensions));
+    }
+}
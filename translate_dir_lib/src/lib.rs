@@ -0,0 +1,6 @@
+pub mod backend;
+pub mod cache;
+pub mod errors;
+pub mod ignore;
+pub mod project;
+pub mod project_config;
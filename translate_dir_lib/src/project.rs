@@ -0,0 +1,468 @@
+use crate::backend::{Backend, BackendConfig};
+use crate::cache::{
+    hash_file, StatusEntry, TranslateAllSummary, TranslationCache, TranslationOutcome,
+    TranslationState,
+};
+use crate::errors::project_errors::{
+    AddLangError, AddTranslatableFileError, InitError, ListError, LoadError, RemoveLangError,
+    SaveError, SetBackendError, SetLayoutError, SetSourceError, StatusError, SyncError,
+    TranslateError,
+};
+use crate::ignore::{expand_extension_filters, matches_extension, walk_files, IgnoreMatcher};
+use crate::project_config::{OutputLayout, ProjectConfig};
+use std::path::{Path, PathBuf};
+use unic_langid::LanguageIdentifier;
+
+const CONFIG_DIR_NAME: &str = ".translate-dir";
+const CONFIG_FILE_NAME: &str = "config.json";
+
+/// An initialized translate-dir project: its on-disk location, the
+/// persisted config and translation-memory cache, and the translation
+/// backend the config selects.
+pub struct Project {
+    root: PathBuf,
+    config_dir: PathBuf,
+    config: ProjectConfig,
+    cache: TranslationCache,
+    backend: Box<dyn Backend>,
+}
+
+/// Initializes a new project named `name` at `path`, writing translations
+/// according to `layout` ("mirrored" or "suffix").
+pub fn init(name: &str, path: PathBuf, layout: &str) -> Result<(), InitError> {
+    let layout =
+        OutputLayout::parse(layout).ok_or_else(|| InitError::InvalidLayout(layout.to_string()))?;
+    std::fs::create_dir_all(&path).map_err(|source| InitError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    let config_dir = path.join(CONFIG_DIR_NAME);
+    std::fs::create_dir_all(&config_dir).map_err(|source| InitError::Io {
+        path: config_dir.clone(),
+        source,
+    })?;
+    let mut config = ProjectConfig::new(name.to_string());
+    config.set_output_layout(layout);
+    let contents = serde_json::to_string_pretty(&config)?;
+    std::fs::write(config_dir.join(CONFIG_FILE_NAME), contents).map_err(|source| {
+        InitError::Io {
+            path: config_dir.join(CONFIG_FILE_NAME),
+            source,
+        }
+    })?;
+    Ok(())
+}
+
+/// Loads an existing project rooted at `path`.
+pub fn load(path: PathBuf) -> Result<Project, LoadError> {
+    let root = path.canonicalize().unwrap_or_else(|_| path.clone());
+    let config_dir = root.join(CONFIG_DIR_NAME);
+    let config_path = config_dir.join(CONFIG_FILE_NAME);
+    if !config_path.exists() {
+        return Err(LoadError::NotAProject(path));
+    }
+    let contents = std::fs::read_to_string(&config_path)?;
+    let config: ProjectConfig = serde_json::from_str(&contents)?;
+    let cache = TranslationCache::load(&config_dir)?;
+    let backend = config.get_backend_config().build();
+    Ok(Project {
+        root,
+        config_dir,
+        config,
+        cache,
+        backend,
+    })
+}
+
+impl Project {
+    pub fn get_config_as_ref(&self) -> &ProjectConfig {
+        &self.config
+    }
+
+    pub fn get_root_path(&self) -> PathBuf {
+        self.root.clone()
+    }
+
+    pub fn get_config_dir_path(&self) -> PathBuf {
+        self.config_dir.clone()
+    }
+
+    pub fn get_backend_env_var_name(&self) -> &str {
+        self.config.get_backend_env_var_name()
+    }
+
+    /// Selects and configures the translation engine this project uses.
+    pub fn set_backend(
+        &mut self,
+        engine: &str,
+        api_key_env: Option<String>,
+        base_url: Option<String>,
+        glossary: Option<String>,
+    ) -> Result<(), SetBackendError> {
+        let backend_config = BackendConfig::new(engine, api_key_env, base_url, glossary)?;
+        self.backend = backend_config.build();
+        self.config.set_backend(backend_config);
+        self.save_config()?;
+        Ok(())
+    }
+
+    /// Overrides the translation backend without touching the persisted
+    /// config, for tests.
+    pub fn set_backend_for_test(&mut self, backend: Box<dyn Backend>) {
+        self.backend = backend;
+    }
+
+    /// Changes where this project writes translations: "mirrored" or
+    /// "suffix".
+    pub fn set_output_layout(&mut self, layout: &str) -> Result<(), SetLayoutError> {
+        let layout = OutputLayout::parse(layout)
+            .ok_or_else(|| SetLayoutError::UnknownLayout(layout.to_string()))?;
+        self.config.set_output_layout(layout);
+        self.save_config()?;
+        Ok(())
+    }
+
+    pub fn set_source_dir(
+        &mut self,
+        dir_name: &str,
+        language: LanguageIdentifier,
+    ) -> Result<(), SetSourceError> {
+        let abs = self.root.join(dir_name);
+        if !abs.is_dir() {
+            return Err(SetSourceError::NotADirectory(abs));
+        }
+        self.config.set_src_dir(language, abs);
+        self.save_config()?;
+        Ok(())
+    }
+
+    pub fn add_lang(&mut self, language: LanguageIdentifier) -> Result<(), AddLangError> {
+        if self.config.lang_dir_for(&language).is_some() {
+            return Err(AddLangError::AlreadyPresent(language.to_string()));
+        }
+        let abs = self.root.join(language.to_string());
+        std::fs::create_dir_all(&abs).map_err(|e| SaveError {
+            path: abs.clone(),
+            source: e,
+        })?;
+        self.config.add_lang_dir(language, abs);
+        self.save_config()?;
+        Ok(())
+    }
+
+    pub fn remove_lang(&mut self, language: LanguageIdentifier) -> Result<(), RemoveLangError> {
+        if !self.config.remove_lang_dir(&language) {
+            return Err(RemoveLangError::NotPresent(language.to_string()));
+        }
+        self.save_config()?;
+        Ok(())
+    }
+
+    pub fn make_translatable_file(
+        &mut self,
+        path: PathBuf,
+    ) -> Result<(), AddTranslatableFileError> {
+        let src_dir = self
+            .config
+            .get_src_dir_path()
+            .ok_or(AddTranslatableFileError::NoSourceDir)?;
+        let abs = to_absolute(&path);
+        if !abs.exists() {
+            return Err(AddTranslatableFileError::NoFile(path));
+        }
+        let relative = abs
+            .strip_prefix(&src_dir)
+            .map_err(|_| AddTranslatableFileError::NotInSourceDir(path.clone()))?
+            .to_path_buf();
+        self.config.add_translatable_file(relative);
+        self.save_config()?;
+        Ok(())
+    }
+
+    pub fn make_untranslatable_file(
+        &mut self,
+        path: PathBuf,
+    ) -> Result<(), AddTranslatableFileError> {
+        let src_dir = self
+            .config
+            .get_src_dir_path()
+            .ok_or(AddTranslatableFileError::NoSourceDir)?;
+        let abs = to_absolute(&path);
+        let relative = abs
+            .strip_prefix(&src_dir)
+            .map_err(|_| AddTranslatableFileError::NotInSourceDir(path.clone()))?
+            .to_path_buf();
+        if !self.config.remove_translatable_file(&relative) {
+            return Err(AddTranslatableFileError::NotTranslatable(path));
+        }
+        self.save_config()?;
+        Ok(())
+    }
+
+    pub fn get_translatable_files(&self) -> Result<Vec<PathBuf>, ListError> {
+        let src_dir = self
+            .config
+            .get_src_dir_path()
+            .ok_or(ListError::NoSourceDir)?;
+        let ignore = IgnoreMatcher::load(&self.root)?;
+        Ok(self
+            .config
+            .get_translatable_files()
+            .iter()
+            .filter(|relative| !ignore.is_ignored(relative))
+            .map(|relative| src_dir.join(relative))
+            .collect())
+    }
+
+    pub fn get_translatable_files_filtered(
+        &self,
+        extension_tokens: &[String],
+    ) -> Result<Vec<PathBuf>, ListError> {
+        let extensions = expand_extension_filters(extension_tokens);
+        Ok(self
+            .get_translatable_files()?
+            .into_iter()
+            .filter(|path| matches_extension(path, &extensions))
+            .collect())
+    }
+
+    /// Walks the source directory (honoring `.translateignore`) and marks
+    /// every file matching `extension_tokens` as translatable. Returns how
+    /// many files were newly marked.
+    pub fn mark_translatable_by_extensions(
+        &mut self,
+        extension_tokens: &[String],
+    ) -> Result<usize, AddTranslatableFileError> {
+        let src_dir = self
+            .config
+            .get_src_dir_path()
+            .ok_or(AddTranslatableFileError::NoSourceDir)?;
+        let extensions = expand_extension_filters(extension_tokens);
+        let ignore = IgnoreMatcher::load(&self.root)?;
+
+        let mut marked = 0;
+        for relative in walk_files(&src_dir, &ignore, &self.config_dir) {
+            if !matches_extension(&relative, &extensions) {
+                continue;
+            }
+            if self.is_suffix_output_file(&relative) {
+                continue;
+            }
+            if self.config.get_translatable_files().contains(&relative) {
+                continue;
+            }
+            self.config.add_translatable_file(relative);
+            marked += 1;
+        }
+        if marked > 0 {
+            self.save_config()?;
+        }
+        Ok(marked)
+    }
+
+    /// Copies every untranslated, non-ignored file from the source directory
+    /// verbatim for each target language: into that language's directory in
+    /// the mirrored layout, or next to the source as `name.<lang>.ext` in the
+    /// suffix layout.
+    pub fn sync_files(&mut self) -> Result<(), SyncError> {
+        let src_dir = self
+            .config
+            .get_src_dir_path()
+            .ok_or(SyncError::NoSourceDir)?;
+        let ignore = IgnoreMatcher::load(&self.root)?;
+        let translatable = self.config.get_translatable_files();
+
+        for relative in walk_files(&src_dir, &ignore, &self.config_dir) {
+            if translatable.contains(&relative) || self.is_suffix_output_file(&relative) {
+                continue;
+            }
+            for lang_dir in self.config.get_lang_dirs_as_ref() {
+                let dest = match self.config.get_output_layout() {
+                    OutputLayout::Mirrored => lang_dir.get_dir_as_ref().get_path().join(&relative),
+                    OutputLayout::Suffix => {
+                        with_lang_suffix(&src_dir.join(&relative), lang_dir.get_lang())
+                    }
+                };
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::copy(src_dir.join(&relative), dest)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `relative` (a path under the source directory) is itself a
+    /// `name.<lang>.ext` translation output, so suffix-layout source walks
+    /// don't re-detect it as a new source file.
+    fn is_suffix_output_file(&self, relative: &Path) -> bool {
+        if self.config.get_output_layout() != OutputLayout::Suffix {
+            return false;
+        }
+        let Some(stem) = relative.file_stem().and_then(|s| s.to_str()) else {
+            return false;
+        };
+        let Some((_, tag)) = stem.rsplit_once('.') else {
+            return false;
+        };
+        let Ok(tag): Result<LanguageIdentifier, _> = tag.parse() else {
+            return false;
+        };
+        self.config
+            .get_lang_dirs_as_ref()
+            .iter()
+            .any(|d| d.get_lang() == &tag)
+    }
+
+    pub fn translate_file(
+        &mut self,
+        path: PathBuf,
+        target_language: LanguageIdentifier,
+        force: bool,
+    ) -> Result<TranslationOutcome, TranslateError> {
+        let src_dir = self
+            .config
+            .get_src_dir_path()
+            .ok_or(TranslateError::NoSourceDir)?;
+        let abs = to_absolute(&path);
+        let relative = abs
+            .strip_prefix(&src_dir)
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|_| path.clone());
+        if !self.config.get_translatable_files().contains(&relative) {
+            return Err(TranslateError::NotTranslatable(path));
+        }
+        if self.config.lang_dir_for(&target_language).is_none() {
+            return Err(TranslateError::UnknownTargetLang(
+                target_language.to_string(),
+            ));
+        }
+
+        let dest = match self.config.get_output_layout() {
+            OutputLayout::Mirrored => self
+                .config
+                .lang_dir_for(&target_language)
+                .expect("checked above")
+                .get_dir_as_ref()
+                .get_path()
+                .join(&relative),
+            OutputLayout::Suffix => with_lang_suffix(&abs, &target_language),
+        };
+        let source_hash = hash_file(&abs)?;
+        let up_to_date = self.cache.get(&relative, &target_language) == Some(source_hash.as_str());
+        if !force && up_to_date && dest.exists() {
+            return Ok(TranslationOutcome::UpToDate);
+        }
+
+        let source_lang = self
+            .config
+            .get_src_dir_as_ref()
+            .expect("checked above")
+            .get_lang()
+            .clone();
+        let source_text = std::fs::read_to_string(&abs)?;
+        let translated = self
+            .backend
+            .translate(&source_text, &source_lang, &target_language)?;
+
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, translated)?;
+        self.cache.set(relative, &target_language, source_hash);
+        self.cache.save(&self.config_dir)?;
+        Ok(TranslationOutcome::Translated)
+    }
+
+    pub fn translate_all(
+        &mut self,
+        target_language: LanguageIdentifier,
+        force: bool,
+    ) -> Result<TranslateAllSummary, TranslateError> {
+        if self.config.lang_dir_for(&target_language).is_none() {
+            return Err(TranslateError::UnknownTargetLang(
+                target_language.to_string(),
+            ));
+        }
+        let files = self.get_translatable_files().map_err(|e| match e {
+            ListError::NoSourceDir => TranslateError::NoSourceDir,
+            ListError::Io(e) => TranslateError::Io(e),
+        })?;
+
+        let mut summary = TranslateAllSummary::default();
+        for abs in files {
+            match self.translate_file(abs, target_language.clone(), force)? {
+                TranslationOutcome::Translated => summary.translated += 1,
+                TranslationOutcome::UpToDate => summary.up_to_date += 1,
+            }
+        }
+        Ok(summary)
+    }
+
+    pub fn status(&self) -> Result<Vec<StatusEntry>, StatusError> {
+        let src_dir = self
+            .config
+            .get_src_dir_path()
+            .ok_or(StatusError::NoSourceDir)?;
+        let files = self.get_translatable_files().map_err(|e| match e {
+            ListError::NoSourceDir => StatusError::NoSourceDir,
+            ListError::Io(e) => StatusError::Io(e),
+        })?;
+        let mut out = Vec::new();
+        for abs in files {
+            let relative = abs.strip_prefix(&src_dir).unwrap_or(&abs);
+            let source_hash = hash_file(&abs)?;
+            for lang_dir in self.config.get_lang_dirs_as_ref() {
+                let target_language = lang_dir.get_lang().clone();
+                let dest = match self.config.get_output_layout() {
+                    OutputLayout::Mirrored => lang_dir.get_dir_as_ref().get_path().join(relative),
+                    OutputLayout::Suffix => with_lang_suffix(&abs, &target_language),
+                };
+                let state = if !dest.exists() {
+                    TranslationState::Missing
+                } else if self.cache.get(relative, &target_language) == Some(source_hash.as_str())
+                {
+                    TranslationState::UpToDate
+                } else {
+                    TranslationState::Stale
+                };
+                out.push(StatusEntry {
+                    file: abs.clone(),
+                    target_language,
+                    state,
+                });
+            }
+        }
+        Ok(out)
+    }
+
+    fn save_config(&self) -> Result<(), SaveError> {
+        let path = self.config_dir.join(CONFIG_FILE_NAME);
+        let contents = serde_json::to_string_pretty(&self.config).map_err(|e| SaveError {
+            path: path.clone(),
+            source: std::io::Error::other(e),
+        })?;
+        std::fs::write(&path, contents).map_err(|source| SaveError {
+            path: path.clone(),
+            source,
+        })
+    }
+}
+
+fn to_absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    }
+}
+
+/// Inserts `lang` before `path`'s extension, e.g. `page.md` -> `page.fr.md`.
+fn with_lang_suffix(path: &Path, lang: &LanguageIdentifier) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let file_name = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{stem}.{lang}.{ext}"),
+        None => format!("{stem}.{lang}"),
+    };
+    path.with_file_name(file_name)
+}
@@ -0,0 +1,189 @@
+use crate::backend::BackendConfig;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use unic_langid::LanguageIdentifier;
+
+/// Where a project writes its translations on disk.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputLayout {
+    /// Mirror the source tree into a directory per target language.
+    #[default]
+    Mirrored,
+    /// Write `name.<lang>.ext` next to each source file.
+    Suffix,
+}
+
+impl OutputLayout {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "mirrored" => Some(Self::Mirrored),
+            "suffix" => Some(Self::Suffix),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Mirrored => "mirrored",
+            Self::Suffix => "suffix",
+        }
+    }
+}
+
+/// A handle to a directory tracked by the project, stored as an absolute
+/// path under the project root.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DirHandle {
+    path: PathBuf,
+}
+
+impl DirHandle {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SrcDirConfig {
+    lang: LanguageIdentifier,
+    dir: DirHandle,
+}
+
+impl SrcDirConfig {
+    pub fn get_lang(&self) -> &LanguageIdentifier {
+        &self.lang
+    }
+
+    pub fn get_dir_as_ref(&self) -> &DirHandle {
+        &self.dir
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LangDirConfig {
+    lang: LanguageIdentifier,
+    dir: DirHandle,
+}
+
+impl LangDirConfig {
+    pub fn get_lang(&self) -> &LanguageIdentifier {
+        &self.lang
+    }
+
+    pub fn get_dir_as_ref(&self) -> &DirHandle {
+        &self.dir
+    }
+}
+
+/// The persisted state of a translate-dir project: its name, source/target
+/// directories, and translatable-file selection.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ProjectConfig {
+    name: String,
+    src_dir: Option<SrcDirConfig>,
+    lang_dirs: Vec<LangDirConfig>,
+    translatable_files: Vec<PathBuf>,
+    #[serde(default)]
+    backend: BackendConfig,
+    #[serde(default)]
+    output_layout: OutputLayout,
+}
+
+impl ProjectConfig {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_src_dir_as_ref(&self) -> Option<&SrcDirConfig> {
+        self.src_dir.as_ref()
+    }
+
+    pub fn get_src_dir_path(&self) -> Option<PathBuf> {
+        self.src_dir
+            .as_ref()
+            .map(|src| src.get_dir_as_ref().get_path().to_path_buf())
+    }
+
+    pub fn get_lang_dirs_as_ref(&self) -> &[LangDirConfig] {
+        &self.lang_dirs
+    }
+
+    pub fn get_translatable_files(&self) -> &[PathBuf] {
+        &self.translatable_files
+    }
+
+    pub fn get_backend_name(&self) -> &str {
+        self.backend.name()
+    }
+
+    pub fn get_backend_env_var_name(&self) -> &str {
+        self.backend.env_var_name()
+    }
+
+    pub fn get_backend_config(&self) -> &BackendConfig {
+        &self.backend
+    }
+
+    pub fn get_output_layout(&self) -> OutputLayout {
+        self.output_layout
+    }
+
+    pub fn get_output_layout_name(&self) -> &'static str {
+        self.output_layout.as_str()
+    }
+
+    pub(crate) fn set_src_dir(&mut self, lang: LanguageIdentifier, abs_path: PathBuf) {
+        self.src_dir = Some(SrcDirConfig {
+            lang,
+            dir: DirHandle::new(abs_path),
+        });
+    }
+
+    pub(crate) fn lang_dir_for(&self, lang: &LanguageIdentifier) -> Option<&LangDirConfig> {
+        self.lang_dirs.iter().find(|d| d.get_lang() == lang)
+    }
+
+    pub(crate) fn add_lang_dir(&mut self, lang: LanguageIdentifier, abs_path: PathBuf) {
+        self.lang_dirs.push(LangDirConfig {
+            lang,
+            dir: DirHandle::new(abs_path),
+        });
+    }
+
+    pub(crate) fn remove_lang_dir(&mut self, lang: &LanguageIdentifier) -> bool {
+        let before = self.lang_dirs.len();
+        self.lang_dirs.retain(|d| d.get_lang() != lang);
+        self.lang_dirs.len() != before
+    }
+
+    pub(crate) fn add_translatable_file(&mut self, relative: PathBuf) {
+        if !self.translatable_files.contains(&relative) {
+            self.translatable_files.push(relative);
+        }
+    }
+
+    pub(crate) fn remove_translatable_file(&mut self, relative: &Path) -> bool {
+        let before = self.translatable_files.len();
+        self.translatable_files.retain(|f| f != relative);
+        self.translatable_files.len() != before
+    }
+
+    pub(crate) fn set_backend(&mut self, backend: BackendConfig) {
+        self.backend = backend;
+    }
+
+    pub(crate) fn set_output_layout(&mut self, layout: OutputLayout) {
+        self.output_layout = layout;
+    }
+}